@@ -1,22 +1,312 @@
 use bevy::{
-    color::palettes::basic::{GRAY, GREEN, BLUE, PURPLE, WHITE},
+    audio::{AddAudioSource, Decodable, Source},
+    color::palettes::basic::{GRAY, GREEN, BLUE, NAVY, OLIVE, PURPLE, RED, SILVER, WHITE, YELLOW},
     prelude::*,
 };
+use bevy_ggrs::{
+    ggrs::{PlayerType, SessionBuilder, UdpNonBlockingSocket},
+    AddRollbackCommandExtension, GgrsAppExtension, GgrsPlugin, GgrsSchedule, LocalInputs,
+    LocalPlayers, PlayerInputs, ReadInputs, Session,
+};
 use bevy_pancam::{PanCam, PanCamPlugin};
 use noise::{NoiseFn, Perlin};
-use rand::Rng;
-use std::collections::HashMap;
+use rand::{Rng, SeedableRng};
+use rand::rngs::StdRng;
+use std::cmp::Reverse;
+use std::collections::{BinaryHeap, HashMap};
+use std::net::SocketAddr;
+use std::sync::atomic::{AtomicU32, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
 
 // Perlin
 const NOISE_SCALE: f64 = 10.3;
 const TILE_SIZE: f32 = 12.;
 const GRID_SIZE: f32 = 64.;
 
-#[derive(Component)]
+const CAMERA_FOLLOW_SPEED: f32 = 6.0;
+const CAMERA_INTRO_SECONDS: f32 = 2.0;
+const CAMERA_INTRO_SCALE: f32 = 6.0;
+
+const INPUT_UP: u8 = 1 << 0;
+const INPUT_DOWN: u8 = 1 << 1;
+const INPUT_LEFT: u8 = 1 << 2;
+const INPUT_RIGHT: u8 = 1 << 3;
+
+/// WASD direction packed into a bitmask so GGRS can send and predict it.
+#[derive(Copy, Clone, PartialEq, bytemuck::Pod, bytemuck::Zeroable)]
+#[repr(C)]
+struct BoxInput {
+    inp: u8,
+}
+
+type GgrsConfig = bevy_ggrs::GgrsConfig<BoxInput, u32>;
+
+#[derive(Component, Clone)]
 struct Player {
     pos: (i32, i32)
 }
 
+/// Identifies which GGRS player handle drives this entity.
+#[derive(Component)]
+struct PlayerHandle(usize);
+
+/// Edge-triggers movement so holding a key steps once instead of every tick.
+#[derive(Component, Clone, Default)]
+struct PrevInput(u8);
+
+/// Tunables for `generate_world`'s layered noise. `seed` comes from the local
+/// `--seed` arg (default 12) and is not exchanged between peers.
+#[derive(Resource)]
+struct WorldConfig {
+    seed: u64,
+    noise_scale: f64,
+    octaves: u32,
+    deep_water_threshold: f64,
+    shallow_water_threshold: f64,
+    sand_threshold: f64,
+    rock_threshold: f64,
+    mountain_threshold: f64,
+    moisture_threshold: f64,
+}
+
+impl Default for WorldConfig {
+    fn default() -> Self {
+        Self {
+            seed: 12,
+            noise_scale: NOISE_SCALE,
+            octaves: 4,
+            deep_water_threshold: -0.5,
+            shallow_water_threshold: -0.2,
+            sand_threshold: -0.05,
+            rock_threshold: 0.35,
+            mountain_threshold: 0.6,
+            moisture_threshold: 0.0,
+        }
+    }
+}
+
+/// The terrain classification for a tile, derived from elevation + moisture.
+#[derive(Clone, Copy, PartialEq, Eq, Hash)]
+enum Biome {
+    DeepWater,
+    ShallowWater,
+    Sand,
+    Grass,
+    Rock,
+    Mountain,
+}
+
+impl Biome {
+    fn passability(self) -> TileType {
+        match self {
+            Biome::DeepWater | Biome::ShallowWater | Biome::Mountain => TileType::Unpassable,
+            Biome::Sand | Biome::Grass | Biome::Rock => TileType::Passable,
+        }
+    }
+}
+
+/// Sums layered octaves of `noise` at `(x, y)`, normalized back to `[-1, 1]`.
+fn sample_octaves(noise: &Perlin, x: i32, y: i32, config: &WorldConfig) -> f64 {
+    let mut amplitude = 1.0;
+    let mut frequency = 1.0;
+    let mut sum = 0.0;
+    let mut max_amplitude = 0.0;
+
+    for _ in 0..config.octaves {
+        let nx = x as f64 / config.noise_scale * frequency;
+        let ny = y as f64 / config.noise_scale * frequency;
+        sum += noise.get([nx, ny]) * amplitude;
+        max_amplitude += amplitude;
+        amplitude *= 0.5;
+        frequency *= 2.0;
+    }
+
+    sum / max_amplitude
+}
+
+/// Classifies a tile from its elevation and moisture samples into a `Biome`.
+fn classify_biome(elevation: f64, moisture: f64, config: &WorldConfig) -> Biome {
+    if elevation < config.deep_water_threshold {
+        Biome::DeepWater
+    } else if elevation < config.shallow_water_threshold {
+        Biome::ShallowWater
+    } else if elevation < config.sand_threshold {
+        if moisture < config.moisture_threshold {
+            Biome::Sand
+        } else {
+            Biome::Grass
+        }
+    } else if elevation < config.rock_threshold {
+        Biome::Grass
+    } else if elevation < config.mountain_threshold {
+        Biome::Rock
+    } else {
+        Biome::Mountain
+    }
+}
+
+const ENEMY_COUNT: usize = 6;
+
+/// Which side an entity fights for; enemies hostile to the player pursue it.
+#[derive(Component)]
+struct Faction {
+    hostile_to_player: bool,
+}
+
+#[derive(Component)]
+struct Enemy {
+    pos: (i32, i32),
+}
+
+/// Present on an `Enemy` until it has acted this player turn.
+#[derive(Component)]
+struct MyTurn;
+
+/// Fired once `move_player`/`follow_path` lands the player on a new tile.
+#[derive(Event)]
+struct PlayerMoved;
+
+/// Fired at an `Enemy` entity when it lands an attack on the player.
+#[derive(EntityEvent)]
+struct Attacked;
+
+/// Fired once an `Attacked` flash has run its course, to recolor back.
+#[derive(EntityEvent)]
+struct AttackEnded;
+
+/// Counts down the `Attacked` flash before triggering `AttackEnded`.
+#[derive(Component)]
+struct FlashTimer(Timer);
+
+/// Marks the camera that eases toward the player each frame.
+#[derive(Component)]
+struct PlayerCamera {
+    speed: f32,
+}
+
+/// Drives the startup zoom-out-then-zoom-in flyover before `camera_follow`.
+#[derive(Component)]
+struct CameraIntro {
+    timer: Timer,
+    start_translation: Vec3,
+    start_scale: f32,
+    end_scale: f32,
+}
+
+/// Fired on keyboard/click-to-move steps, not from the resimulated `move_players`.
+#[derive(Event)]
+struct StepSfx;
+
+/// Fired when a move is rejected by `TileMap::is_passable`.
+#[derive(Event)]
+struct BlockedSfx;
+
+/// Fired when an `Enemy` lands a hit on the player.
+#[derive(Event)]
+struct EnemyAttackSfx;
+
+/// Fired when the player's tile changes biome.
+#[derive(Event)]
+struct BiomeEntrySfx(Biome);
+
+/// A synthesized attack/decay envelope over a sine tone, retriggered by bumping `trigger`.
+#[derive(Asset, TypePath, Clone)]
+struct EnvelopeVoice {
+    frequency: f32,
+    attack_secs: f32,
+    decay_secs: f32,
+    trigger: Arc<AtomicU32>,
+}
+
+struct EnvelopeDecoder {
+    frequency: f32,
+    attack_secs: f32,
+    decay_secs: f32,
+    trigger: Arc<AtomicU32>,
+    seen_trigger: u32,
+    sample_rate: u32,
+    elapsed_samples: u32,
+}
+
+impl Decodable for EnvelopeVoice {
+    type DecoderItem = f32;
+    type Decoder = EnvelopeDecoder;
+
+    fn decoder(&self) -> Self::Decoder {
+        EnvelopeDecoder {
+            frequency: self.frequency,
+            attack_secs: self.attack_secs,
+            decay_secs: self.decay_secs,
+            trigger: self.trigger.clone(),
+            seen_trigger: self.trigger.load(Ordering::Relaxed),
+            sample_rate: 44_100,
+            elapsed_samples: 0,
+        }
+    }
+}
+
+impl Iterator for EnvelopeDecoder {
+    type Item = f32;
+
+    fn next(&mut self) -> Option<f32> {
+        // A retrigger from the ECS side restarts the envelope from the top.
+        let current_trigger = self.trigger.load(Ordering::Relaxed);
+        if current_trigger != self.seen_trigger {
+            self.seen_trigger = current_trigger;
+            self.elapsed_samples = 0;
+        }
+
+        let t = self.elapsed_samples as f32 / self.sample_rate as f32;
+        self.elapsed_samples += 1;
+
+        let envelope = if t < self.attack_secs {
+            t / self.attack_secs
+        } else {
+            (1.0 - (t - self.attack_secs) / self.decay_secs).max(0.0)
+        };
+
+        let phase = t * self.frequency * std::f32::consts::TAU;
+        Some(phase.sin() * envelope)
+    }
+}
+
+impl Source for EnvelopeDecoder {
+    fn current_frame_len(&self) -> Option<usize> {
+        None
+    }
+
+    fn channels(&self) -> u16 {
+        1
+    }
+
+    fn sample_rate(&self) -> u32 {
+        self.sample_rate
+    }
+
+    fn total_duration(&self) -> Option<Duration> {
+        None
+    }
+}
+
+/// The handful of always-on voices, each holding its own retrigger counter.
+#[derive(Resource)]
+struct SynthVoices {
+    step: Arc<AtomicU32>,
+    blocked: Arc<AtomicU32>,
+    enemy_attack: Arc<AtomicU32>,
+    biome: HashMap<Biome, Arc<AtomicU32>>,
+}
+
+/// Gameplay events queued between the synth's own 20 fps polling ticks.
+#[derive(Resource, Default)]
+struct PendingTriggers {
+    step: bool,
+    blocked: bool,
+    enemy_attack: bool,
+    biome: Option<Biome>,
+}
+
 #[derive(Component)]
 struct Tile {
     pos: (i32, i32),
@@ -34,31 +324,233 @@ struct TileMap {
     tiles: HashMap<(i32, i32), TileType>
 }
 
+/// Which `Biome` generated each tile, kept alongside `TileMap` for gameplay systems.
+#[derive(Resource, Default)]
+struct BiomeMap(HashMap<(i32, i32), Biome>);
+
 impl TileMap {
     fn new() -> Self {
         Self {
             tiles: HashMap::new()
         }
     }
-    
+
     fn is_passable(&self, pos: (i32, i32)) -> bool {
         self.tiles.get(&pos)
             .map(|t| *t == TileType::Passable)
             .unwrap_or(false)
     }
-    
+
     fn insert(&mut self, pos: (i32, i32), ttype: TileType) {
         self.tiles.insert(pos, ttype);
     }
+
+    /// A* over the 4-connected grid; `None` if `goal` is unreachable.
+    fn find_path(&self, start: (i32, i32), goal: (i32, i32)) -> Option<Vec<(i32, i32)>> {
+        if !self.is_passable(goal) {
+            return None;
+        }
+
+        let mut open = BinaryHeap::new();
+        let mut came_from: HashMap<(i32, i32), (i32, i32)> = HashMap::new();
+        let mut g_score: HashMap<(i32, i32), i32> = HashMap::new();
+
+        g_score.insert(start, 0);
+        open.push(Reverse((manhattan(start, goal), start)));
+
+        while let Some(Reverse((_, current))) = open.pop() {
+            if current == goal {
+                return Some(reconstruct_path(&came_from, current));
+            }
+
+            let g = g_score[&current];
+            for neighbor in neighbors(current) {
+                if !self.is_passable(neighbor) {
+                    continue;
+                }
+
+                let tentative_g = g + 1;
+                if tentative_g < *g_score.get(&neighbor).unwrap_or(&i32::MAX) {
+                    came_from.insert(neighbor, current);
+                    g_score.insert(neighbor, tentative_g);
+                    open.push(Reverse((tentative_g + manhattan(neighbor, goal), neighbor)));
+                }
+            }
+        }
+
+        None
+    }
+}
+
+fn manhattan(a: (i32, i32), b: (i32, i32)) -> i32 {
+    (a.0 - b.0).abs() + (a.1 - b.1).abs()
+}
+
+fn neighbors(pos: (i32, i32)) -> [(i32, i32); 4] {
+    [
+        (pos.0 + 1, pos.1),
+        (pos.0 - 1, pos.1),
+        (pos.0, pos.1 + 1),
+        (pos.0, pos.1 - 1),
+    ]
+}
+
+fn reconstruct_path(
+    came_from: &HashMap<(i32, i32), (i32, i32)>,
+    mut current: (i32, i32),
+) -> Vec<(i32, i32)> {
+    let mut path = vec![current];
+    while let Some(&prev) = came_from.get(&current) {
+        current = prev;
+        path.push(current);
+    }
+    path.reverse();
+    path
+}
+
+/// Queued grid steps for the player's click-to-move walk, popped one per `Update`.
+#[derive(Component, Default)]
+struct PathQueue(Vec<(i32, i32)>);
+
+/// Session setup for the optional GGRS rollback match, read from CLI args.
+struct NetArgs {
+    local_port: u16,
+    peer_addr: Option<SocketAddr>,
+    local_handle: usize,
+    input_delay: usize,
+    max_prediction: usize,
+    seed: u64,
+}
+
+impl Default for NetArgs {
+    fn default() -> Self {
+        Self {
+            local_port: 7000,
+            peer_addr: None,
+            local_handle: 0,
+            input_delay: 2,
+            max_prediction: 8,
+            seed: 12,
+        }
+    }
+}
+
+/// `--peer` opts into a P2P session; without it the game runs single-player.
+/// Exactly one side of a match should pass `--local-handle 1`.
+fn parse_net_args() -> NetArgs {
+    let mut args = NetArgs::default();
+    let mut raw = std::env::args().skip(1);
+
+    while let Some(flag) = raw.next() {
+        match flag.as_str() {
+            "--local-port" => {
+                if let Some(v) = raw.next().and_then(|v| v.parse().ok()) {
+                    args.local_port = v;
+                }
+            }
+            "--peer" => args.peer_addr = raw.next().and_then(|v| v.parse().ok()),
+            "--local-handle" => {
+                if let Some(v) = raw.next().and_then(|v| v.parse::<usize>().ok()) {
+                    if v > 1 {
+                        eprintln!("--local-handle must be 0 or 1, got {v}");
+                        std::process::exit(1);
+                    }
+                    args.local_handle = v;
+                }
+            }
+            "--input-delay" => {
+                if let Some(v) = raw.next().and_then(|v| v.parse().ok()) {
+                    args.input_delay = v;
+                }
+            }
+            "--max-prediction" => {
+                if let Some(v) = raw.next().and_then(|v| v.parse().ok()) {
+                    args.max_prediction = v;
+                }
+            }
+            "--seed" => {
+                if let Some(v) = raw.next().and_then(|v| v.parse().ok()) {
+                    args.seed = v;
+                }
+            }
+            _ => {}
+        }
+    }
+
+    args
 }
 
 fn main() {
-    App::new()
-        .add_plugins((DefaultPlugins, MeshPickingPlugin, PanCamPlugin::default()))
+    let net_args = parse_net_args();
+
+    let mut app = App::new();
+    app.add_plugins((DefaultPlugins, MeshPickingPlugin, PanCamPlugin::default()))
+        .add_audio_source::<EnvelopeVoice>()
         .insert_resource(TileMap::new())
-        .add_systems(Startup, setup)
-        .add_systems(Update, move_player)
-        .run();
+        .init_resource::<BiomeMap>()
+        .init_resource::<PendingTriggers>()
+        .insert_resource(WorldConfig {
+            seed: net_args.seed,
+            ..default()
+        })
+        .add_event::<PlayerMoved>()
+        .add_event::<StepSfx>()
+        .add_event::<BlockedSfx>()
+        .add_event::<EnemyAttackSfx>()
+        .add_event::<BiomeEntrySfx>()
+        .add_systems(Startup, (setup, setup_synth))
+        .add_systems(
+            Update,
+            (
+                move_player,
+                follow_path,
+                (start_enemy_turns, enemy_turn).chain(),
+                detect_biome_entry,
+            )
+                .run_if(not(resource_exists::<Session<GgrsConfig>>)),
+        )
+        .add_systems(Update, tick_flash_timers)
+        .add_systems(
+            Update,
+            (animate_camera_intro, camera_follow)
+                .chain()
+                // Both systems assume a single `Player` entity; the 2-player
+                // GGRS session spawns two, so `.single()` would always fail.
+                // Multiplayer has no camera follow yet.
+                .run_if(not(resource_exists::<Session<GgrsConfig>>)),
+        )
+        .add_systems(Update, (queue_synth_triggers, poll_synth_triggers).chain());
+
+    if let Some(peer_addr) = net_args.peer_addr {
+        let socket = UdpNonBlockingSocket::bind_to_port(net_args.local_port)
+            .expect("failed to bind GGRS socket");
+        let local_handle = net_args.local_handle;
+        let remote_handle = 1 - local_handle;
+
+        let session = SessionBuilder::<GgrsConfig>::new()
+            .with_num_players(2)
+            .with_input_delay(net_args.input_delay)
+            .with_max_prediction_window(net_args.max_prediction)
+            .expect("invalid max prediction window")
+            .add_player(PlayerType::Local, local_handle)
+            .expect("failed to add local player")
+            .add_player(PlayerType::Remote(peer_addr), remote_handle)
+            .expect("failed to add remote player")
+            .start_p2p_session(socket)
+            .expect("failed to start GGRS session");
+
+        app.add_plugins(GgrsPlugin::<GgrsConfig>::default())
+            .set_rollback_schedule_fps(60)
+            .rollback_component_with_clone::<Transform>()
+            .rollback_component_with_clone::<Player>()
+            .rollback_component_with_clone::<PrevInput>()
+            .insert_resource(LocalPlayers(vec![local_handle]))
+            .insert_resource(Session::P2P(session))
+            .add_systems(ReadInputs, read_local_inputs)
+            .add_systems(GgrsSchedule, move_players);
+    }
+
+    app.run();
 }
 
 fn setup(
@@ -66,32 +558,92 @@ fn setup(
     mut meshes: ResMut<Assets<Mesh>>,
     mut materials: ResMut<Assets<ColorMaterial>>,
     mut tile_map: ResMut<TileMap>,
+    mut biome_map: ResMut<BiomeMap>,
+    world_config: Res<WorldConfig>,
+    local_players: Option<Res<LocalPlayers>>,
 ) {
     let hover_matl = materials.add(Color::from(WHITE));
     let default_matl = materials.add(Color::from(PURPLE));
     let player_matl = materials.add(Color::from(GREEN));
-    
-    commands.spawn((Camera2d, PanCam::default()));
-    
-    generate_world(&mut commands, &mut meshes, &mut materials, &mut tile_map);
-    
-    // Spawn player at the center of the tile grid
-    let player_grid_pos = (GRID_SIZE as i32 / 2, GRID_SIZE as i32 / 2);
-    let player_pos = Vec3::new(
-        player_grid_pos.0 as f32 * TILE_SIZE,
-        player_grid_pos.1 as f32 * TILE_SIZE,
-        10.0, // Higher z-index to render above tiles
+
+    // Start the intro zoomed out over the whole grid, centered on it; the
+    // flyover then eases this scale from `CAMERA_INTRO_SCALE` down to 1.0
+    // and over onto the player.
+    let world_center = Vec3::new(
+        GRID_SIZE / 2.0 * TILE_SIZE,
+        GRID_SIZE / 2.0 * TILE_SIZE,
+        999.0,
     );
 
     commands.spawn((
-        Player { pos: player_grid_pos },
-        Mesh2d(meshes.add(Rectangle::default())),
-        MeshMaterial2d(player_matl),
-        Transform::default()
-            .with_scale(Vec3::splat(TILE_SIZE))
-            .with_translation(player_pos),
+        Camera2d,
+        PanCam::default(),
+        PlayerCamera { speed: CAMERA_FOLLOW_SPEED },
+        CameraIntro {
+            timer: Timer::from_seconds(CAMERA_INTRO_SECONDS, TimerMode::Once),
+            start_translation: world_center,
+            start_scale: CAMERA_INTRO_SCALE,
+            end_scale: 1.0,
+        },
+        Projection::Orthographic(OrthographicProjection {
+            scale: CAMERA_INTRO_SCALE,
+            ..OrthographicProjection::default_2d()
+        }),
+        Transform::from_translation(world_center),
     ));
 
+    generate_world(
+        &mut commands,
+        &mut meshes,
+        &mut materials,
+        &mut tile_map,
+        &mut biome_map,
+        &world_config,
+    );
+
+    // Spawn player(s) at the center of the tile grid
+    let player_grid_pos = (GRID_SIZE as i32 / 2, GRID_SIZE as i32 / 2);
+
+    if local_players.is_some() {
+        // Networked match: one entity per GGRS handle, nudged apart so they don't overlap.
+        for handle in 0..2 {
+            let offset = if handle == 0 { -1 } else { 1 };
+            let pos = (player_grid_pos.0 + offset, player_grid_pos.1);
+
+            commands
+                .spawn((
+                    Player { pos },
+                    PlayerHandle(handle),
+                    PrevInput::default(),
+                    Mesh2d(meshes.add(Rectangle::default())),
+                    MeshMaterial2d(player_matl.clone()),
+                    Transform::default()
+                        .with_scale(Vec3::splat(TILE_SIZE))
+                        .with_translation(Vec3::new(
+                            pos.0 as f32 * TILE_SIZE,
+                            pos.1 as f32 * TILE_SIZE,
+                            10.0, // Higher z-index to render above tiles
+                        )),
+                ))
+                .add_rollback();
+        }
+    } else {
+        let player_pos = Vec3::new(
+            player_grid_pos.0 as f32 * TILE_SIZE,
+            player_grid_pos.1 as f32 * TILE_SIZE,
+            10.0, // Higher z-index to render above tiles
+        );
+
+        commands.spawn((
+            Player { pos: player_grid_pos },
+            Mesh2d(meshes.add(Rectangle::default())),
+            MeshMaterial2d(player_matl),
+            Transform::default()
+                .with_scale(Vec3::splat(TILE_SIZE))
+                .with_translation(player_pos),
+        ));
+    }
+
     commands.spawn((
         Text::new("Move the light with WASD.\nThe camera will smoothly track the light."),
         Node {
@@ -105,14 +657,18 @@ fn setup(
 }
 
 fn move_player(
-    mut player_query: Query<(&mut Player, &mut Transform)>,
+    mut commands: Commands,
+    mut player_query: Query<(Entity, &mut Player, &mut Transform, Option<&PathQueue>)>,
     kb_input: Res<ButtonInput<KeyCode>>,
     tile_map: Res<TileMap>,
+    mut player_moved: EventWriter<PlayerMoved>,
+    mut step_sfx: EventWriter<StepSfx>,
+    mut blocked_sfx: EventWriter<BlockedSfx>,
 ) {
-    let Ok((mut player, mut transform)) = player_query.single_mut() else {
+    let Ok((entity, mut player, mut transform, path)) = player_query.single_mut() else {
         return;
     };
-    
+
     let mut direction = (0, 0);
 
     // Use just_pressed instead of pressed for single tile movement per key press
@@ -132,18 +688,410 @@ fn move_player(
         direction.0 += 1;
     }
 
+    if direction == (0, 0) {
+        return;
+    }
+
+    // Manual input interrupts an in-flight click-to-move path instead of
+    // stacking a second move onto the same frame `follow_path` pops one.
+    if path.is_some() {
+        commands.entity(entity).remove::<PathQueue>();
+        return;
+    }
+
     // Move exactly one tile in the direction if valid
-    if direction != (0, 0) {
-        let new_pos = (player.pos.0 + direction.0, player.pos.1 + direction.1);
-        
-        // Check if the new position is passable
-        if tile_map.is_passable(new_pos) {
-            player.pos = new_pos;
-            transform.translation = Vec3::new(
-                new_pos.0 as f32 * TILE_SIZE,
-                new_pos.1 as f32 * TILE_SIZE,
-                transform.translation.z,
-            );
+    let new_pos = (player.pos.0 + direction.0, player.pos.1 + direction.1);
+
+    // Check if the new position is passable
+    if tile_map.is_passable(new_pos) {
+        player.pos = new_pos;
+        transform.translation = Vec3::new(
+            new_pos.0 as f32 * TILE_SIZE,
+            new_pos.1 as f32 * TILE_SIZE,
+            transform.translation.z,
+        );
+        player_moved.write(PlayerMoved);
+        step_sfx.write(StepSfx);
+    } else {
+        blocked_sfx.write(BlockedSfx);
+    }
+}
+
+/// Packs each local player's WASD state into a `BoxInput` for GGRS to send and predict.
+fn read_local_inputs(mut commands: Commands, local_players: Res<LocalPlayers>, kb_input: Res<ButtonInput<KeyCode>>) {
+    let mut local_inputs = HashMap::new();
+
+    for handle in &local_players.0 {
+        let mut inp = 0u8;
+
+        if kb_input.pressed(KeyCode::KeyW) {
+            inp |= INPUT_UP;
+        }
+        if kb_input.pressed(KeyCode::KeyS) {
+            inp |= INPUT_DOWN;
+        }
+        if kb_input.pressed(KeyCode::KeyA) {
+            inp |= INPUT_LEFT;
+        }
+        if kb_input.pressed(KeyCode::KeyD) {
+            inp |= INPUT_RIGHT;
+        }
+
+        local_inputs.insert(*handle, BoxInput { inp });
+    }
+
+    commands.insert_resource(LocalInputs::<GgrsConfig>(local_inputs));
+}
+
+/// The networked twin of `move_player`, reading GGRS input instead of `Res<ButtonInput>`.
+fn move_players(
+    mut player_query: Query<(&mut Player, &mut Transform, &PlayerHandle, &mut PrevInput)>,
+    inputs: Res<PlayerInputs<GgrsConfig>>,
+    tile_map: Res<TileMap>,
+) {
+    for (mut player, mut transform, handle, mut prev) in &mut player_query {
+        let (input, _) = inputs[handle.0];
+
+        // Edge-trigger on newly-pressed bits so holding a key still steps one tile at a time.
+        let pressed = input.inp & !prev.0;
+        prev.0 = input.inp;
+
+        let mut direction = (0, 0);
+        if pressed & INPUT_UP != 0 {
+            direction.1 += 1;
+        }
+        if pressed & INPUT_DOWN != 0 {
+            direction.1 -= 1;
+        }
+        if pressed & INPUT_LEFT != 0 {
+            direction.0 -= 1;
+        }
+        if pressed & INPUT_RIGHT != 0 {
+            direction.0 += 1;
+        }
+
+        if direction != (0, 0) {
+            let new_pos = (player.pos.0 + direction.0, player.pos.1 + direction.1);
+
+            if tile_map.is_passable(new_pos) {
+                player.pos = new_pos;
+                transform.translation = Vec3::new(
+                    new_pos.0 as f32 * TILE_SIZE,
+                    new_pos.1 as f32 * TILE_SIZE,
+                    transform.translation.z,
+                );
+            }
+        }
+    }
+}
+
+/// Walks the player one tile along its queued `PathQueue` per call.
+fn follow_path(
+    mut commands: Commands,
+    mut player_query: Query<(Entity, &mut Player, &mut Transform, &mut PathQueue)>,
+    tile_map: Res<TileMap>,
+    mut step_sfx: EventWriter<StepSfx>,
+    mut player_moved: EventWriter<PlayerMoved>,
+) {
+    let Ok((entity, mut player, mut transform, mut path)) = player_query.single_mut() else {
+        return;
+    };
+
+    let Some(next) = path.0.first().copied() else {
+        commands.entity(entity).remove::<PathQueue>();
+        return;
+    };
+
+    // The map can change out from under a queued path; abort cleanly if so.
+    if !tile_map.is_passable(next) {
+        commands.entity(entity).remove::<PathQueue>();
+        return;
+    }
+
+    player.pos = next;
+    transform.translation = Vec3::new(
+        next.0 as f32 * TILE_SIZE,
+        next.1 as f32 * TILE_SIZE,
+        transform.translation.z,
+    );
+    path.0.remove(0);
+    step_sfx.write(StepSfx);
+    player_moved.write(PlayerMoved);
+}
+
+/// Queues a click-to-move path from the player to `tile_pos` when the tile is clicked.
+fn set_destination_on_click(
+    tile_pos: (i32, i32),
+) -> impl Fn(On<Pointer<Click>>, Query<(Entity, &Player)>, Res<TileMap>, Commands) {
+    move |_event, player_query, tile_map, mut commands| {
+        let Ok((entity, player)) = player_query.single() else {
+            return;
+        };
+
+        if let Some(path) = tile_map.find_path(player.pos, tile_pos) {
+            // Skip the starting tile; only the steps ahead need to be walked.
+            commands
+                .entity(entity)
+                .insert(PathQueue(path.into_iter().skip(1).collect()));
+        }
+    }
+}
+
+/// Arms every `Enemy` to act once the player has stepped.
+fn start_enemy_turns(
+    mut commands: Commands,
+    mut player_moved: EventReader<PlayerMoved>,
+    enemy_query: Query<Entity, With<Enemy>>,
+) {
+    if player_moved.is_empty() {
+        return;
+    }
+    player_moved.clear();
+
+    for entity in &enemy_query {
+        commands.entity(entity).insert(MyTurn);
+    }
+}
+
+/// Each armed `Enemy` attacks the player if adjacent, else steps toward them.
+fn enemy_turn(
+    mut commands: Commands,
+    mut enemy_query: Query<(Entity, &mut Enemy, &Faction, &mut Transform), With<MyTurn>>,
+    player_query: Query<&Player>,
+    tile_map: Res<TileMap>,
+    mut enemy_attack_sfx: EventWriter<EnemyAttackSfx>,
+) {
+    let Ok(player) = player_query.single() else {
+        return;
+    };
+
+    for (entity, mut enemy, faction, mut transform) in &mut enemy_query {
+        commands.entity(entity).remove::<MyTurn>();
+
+        if !faction.hostile_to_player {
+            continue;
+        }
+
+        if manhattan(enemy.pos, player.pos) == 1 {
+            commands.entity(entity).trigger(Attacked);
+            enemy_attack_sfx.write(EnemyAttackSfx);
+            continue;
+        }
+
+        let Some(path) = tile_map.find_path(enemy.pos, player.pos) else {
+            continue;
+        };
+
+        // path[0] is the enemy's own tile; path[1] is the next step toward the player.
+        let Some(&next) = path.get(1) else {
+            continue;
+        };
+
+        enemy.pos = next;
+        transform.translation = Vec3::new(
+            next.0 as f32 * TILE_SIZE,
+            next.1 as f32 * TILE_SIZE,
+            transform.translation.z,
+        );
+    }
+}
+
+/// Starts the attack-flash countdown when an `Enemy` lands a hit.
+fn start_flash_timer(attacked: On<Attacked>, mut commands: Commands) {
+    commands
+        .entity(attacked.event_target())
+        .insert(FlashTimer(Timer::from_seconds(0.15, TimerMode::Once)));
+}
+
+/// Ticks `FlashTimer`s and fires `AttackEnded` once it runs out.
+fn tick_flash_timers(
+    mut commands: Commands,
+    time: Res<Time>,
+    mut query: Query<(Entity, &mut FlashTimer)>,
+) {
+    for (entity, mut timer) in &mut query {
+        if timer.0.tick(time.delta()).just_finished() {
+            commands.entity(entity).trigger(AttackEnded);
+            commands.entity(entity).remove::<FlashTimer>();
+        }
+    }
+}
+
+/// Eases the camera from a whole-grid framing onto the player, then hands off to `camera_follow`.
+fn animate_camera_intro(
+    mut commands: Commands,
+    time: Res<Time>,
+    player_query: Query<&Transform, (With<Player>, Without<PlayerCamera>)>,
+    mut camera_query: Query<(Entity, &mut CameraIntro, &mut Transform, &mut Projection), With<PlayerCamera>>,
+) {
+    let Ok(player_transform) = player_query.single() else {
+        return;
+    };
+    let Ok((entity, mut intro, mut transform, mut projection)) = camera_query.single_mut() else {
+        return;
+    };
+
+    let t = intro.timer.tick(time.delta()).fraction();
+    transform.translation = intro.start_translation.lerp(player_transform.translation, t);
+
+    if let Projection::Orthographic(ortho) = &mut *projection {
+        ortho.scale = intro.start_scale + (intro.end_scale - intro.start_scale) * t;
+    }
+
+    if intro.timer.finished() {
+        commands.entity(entity).remove::<CameraIntro>();
+    }
+}
+
+/// Eases the camera toward the player each frame; skips while dragging with `PanCam`.
+fn camera_follow(
+    time: Res<Time>,
+    mouse_input: Res<ButtonInput<MouseButton>>,
+    player_query: Query<&Transform, (With<Player>, Without<PlayerCamera>)>,
+    mut camera_query: Query<(&PlayerCamera, &PanCam, &mut Transform), Without<CameraIntro>>,
+) {
+    let Ok(player_transform) = player_query.single() else {
+        return;
+    };
+    let Ok((camera, pan_cam, mut camera_transform)) = camera_query.single_mut() else {
+        return;
+    };
+
+    // Let the user's manual drag win over the automatic follow.
+    if pan_cam.grab_buttons.iter().any(|button| mouse_input.pressed(*button)) {
+        return;
+    }
+
+    let target = player_transform
+        .translation
+        .xy()
+        .extend(camera_transform.translation.z);
+    let ease = (camera.speed * time.delta_secs()).min(1.0);
+    camera_transform.translation = camera_transform.translation.lerp(target, ease);
+}
+
+/// Spawns one always-on `AudioPlayer` per voice: steps, blocked moves, enemy attacks, and one per biome.
+fn setup_synth(mut commands: Commands, mut voices_assets: ResMut<Assets<EnvelopeVoice>>) {
+    let step = Arc::new(AtomicU32::new(0));
+    let blocked = Arc::new(AtomicU32::new(0));
+    let enemy_attack = Arc::new(AtomicU32::new(0));
+
+    commands.spawn(AudioPlayer(voices_assets.add(EnvelopeVoice {
+        frequency: 880.0,
+        attack_secs: 0.005,
+        decay_secs: 0.08,
+        trigger: step.clone(),
+    })));
+    commands.spawn(AudioPlayer(voices_assets.add(EnvelopeVoice {
+        frequency: 110.0,
+        attack_secs: 0.01,
+        decay_secs: 0.2,
+        trigger: blocked.clone(),
+    })));
+    commands.spawn(AudioPlayer(voices_assets.add(EnvelopeVoice {
+        frequency: 220.0,
+        attack_secs: 0.01,
+        decay_secs: 0.25,
+        trigger: enemy_attack.clone(),
+    })));
+
+    let mut biome = HashMap::new();
+    for (biome_kind, frequency) in [
+        (Biome::DeepWater, 60.0),
+        (Biome::ShallowWater, 140.0),
+        (Biome::Sand, 300.0),
+        (Biome::Grass, 440.0),
+        (Biome::Rock, 180.0),
+        (Biome::Mountain, 90.0),
+    ] {
+        let trigger = Arc::new(AtomicU32::new(0));
+        commands.spawn(AudioPlayer(voices_assets.add(EnvelopeVoice {
+            frequency,
+            attack_secs: 0.02,
+            decay_secs: 0.3,
+            trigger: trigger.clone(),
+        })));
+        biome.insert(biome_kind, trigger);
+    }
+
+    commands.insert_resource(SynthVoices {
+        step,
+        blocked,
+        enemy_attack,
+        biome,
+    });
+}
+
+/// Watches the player's tile for a biome change and fires `BiomeEntrySfx`.
+fn detect_biome_entry(
+    player_query: Query<&Player>,
+    biome_map: Res<BiomeMap>,
+    mut last_biome: Local<Option<Biome>>,
+    mut biome_entry_events: EventWriter<BiomeEntrySfx>,
+) {
+    let Ok(player) = player_query.single() else {
+        return;
+    };
+
+    let biome = biome_map.0.get(&player.pos).copied();
+    if biome != *last_biome {
+        *last_biome = biome;
+        if let Some(biome) = biome {
+            biome_entry_events.write(BiomeEntrySfx(biome));
+        }
+    }
+}
+
+/// Drains the frame's gameplay sfx events into `PendingTriggers`.
+fn queue_synth_triggers(
+    mut pending: ResMut<PendingTriggers>,
+    mut step_events: EventReader<StepSfx>,
+    mut blocked_events: EventReader<BlockedSfx>,
+    mut enemy_attack_events: EventReader<EnemyAttackSfx>,
+    mut biome_entry_events: EventReader<BiomeEntrySfx>,
+) {
+    if step_events.read().count() > 0 {
+        pending.step = true;
+    }
+    if blocked_events.read().count() > 0 {
+        pending.blocked = true;
+    }
+    if enemy_attack_events.read().count() > 0 {
+        pending.enemy_attack = true;
+    }
+    if let Some(BiomeEntrySfx(biome)) = biome_entry_events.read().last() {
+        pending.biome = Some(*biome);
+    }
+}
+
+/// Polls `PendingTriggers` on its own 20 fps clock and bumps the matching voice's trigger.
+fn poll_synth_triggers(
+    time: Res<Time>,
+    mut clock: Local<Option<Timer>>,
+    mut pending: ResMut<PendingTriggers>,
+    voices: Option<Res<SynthVoices>>,
+) {
+    let Some(voices) = voices else {
+        return;
+    };
+    let clock = clock.get_or_insert_with(|| Timer::from_seconds(1.0 / 20.0, TimerMode::Repeating));
+
+    if !clock.tick(time.delta()).just_finished() {
+        return;
+    }
+
+    if std::mem::take(&mut pending.step) {
+        voices.step.fetch_add(1, Ordering::Relaxed);
+    }
+    if std::mem::take(&mut pending.blocked) {
+        voices.blocked.fetch_add(1, Ordering::Relaxed);
+    }
+    if std::mem::take(&mut pending.enemy_attack) {
+        voices.enemy_attack.fetch_add(1, Ordering::Relaxed);
+    }
+    if let Some(biome) = pending.biome.take() {
+        if let Some(trigger) = voices.biome.get(&biome) {
+            trigger.fetch_add(1, Ordering::Relaxed);
         }
     }
 }
@@ -153,25 +1101,42 @@ fn generate_world(
     meshes: &mut ResMut<Assets<Mesh>>,
     materials: &mut ResMut<Assets<ColorMaterial>>,
     tile_map: &mut ResMut<TileMap>,
+    biome_map: &mut ResMut<BiomeMap>,
+    config: &WorldConfig,
 ) {
-    let mut rng = rand::thread_rng();
-    let perlin = Perlin::new(12);
-    
+    let mut rng = StdRng::seed_from_u64(config.seed);
+    let elevation_noise = Perlin::new(config.seed as u32);
+    // A second, independent noise field for moisture.
+    let moisture_noise = Perlin::new((config.seed as u32).wrapping_add(1_000));
+
+    let deep_water_matl = materials.add(Color::from(NAVY));
+    let shallow_water_matl = materials.add(Color::from(BLUE));
+    let sand_matl = materials.add(Color::from(OLIVE));
+    let grass_matl = materials.add(Color::from(GREEN));
     let rock_matl = materials.add(Color::from(GRAY));
-    let water_matl = materials.add(Color::from(BLUE));
-    let ground_matl = materials.add(Color::from(PURPLE));
+    let mountain_matl = materials.add(Color::from(SILVER));
     let hover_matl = materials.add(Color::from(WHITE));
-    
+
     for x in 0..GRID_SIZE as i32 {
         for y in 0..GRID_SIZE as i32 {
-            let noise_val = perlin.get([x as f64 / NOISE_SCALE, y as f64 / NOISE_SCALE]);
-            
-            // Spawn ground tile
-            let ground_material = ground_matl.clone();
+            let elevation = sample_octaves(&elevation_noise, x, y, config);
+            let moisture = sample_octaves(&moisture_noise, x, y, config);
+            let biome = classify_biome(elevation, moisture, config);
+
+            let material = match biome {
+                Biome::DeepWater => deep_water_matl.clone(),
+                Biome::ShallowWater => shallow_water_matl.clone(),
+                Biome::Sand => sand_matl.clone(),
+                Biome::Grass => grass_matl.clone(),
+                Biome::Rock => rock_matl.clone(),
+                Biome::Mountain => mountain_matl.clone(),
+            };
+            let ttype = biome.passability();
+
             commands.spawn((
-                Tile { pos: (x, y), ttype: TileType::Passable },
+                Tile { pos: (x, y), ttype },
                 Mesh2d(meshes.add(Rectangle::default())),
-                MeshMaterial2d(ground_material.clone()),
+                MeshMaterial2d(material.clone()),
                 Transform::default()
                     .with_scale(Vec3::splat(TILE_SIZE))
                     .with_translation(Vec3::new(
@@ -182,59 +1147,47 @@ fn generate_world(
                 Pickable::default(),
             ))
             .observe(recolor_on::<Pointer<Over>>(hover_matl.clone()))
-            .observe(recolor_on::<Pointer<Out>>(ground_material.clone()));
-            
-            // Track as passable in tile map
-            tile_map.insert((x, y), TileType::Passable);
-            
-            // Spawn rock on top if noise is high enough
-            if noise_val > 0.3 {
-                let rock_material = rock_matl.clone();
-                commands.spawn((
-                    Tile { pos: (x, y), ttype: TileType::Unpassable },
-                    Mesh2d(meshes.add(Rectangle::default())),
-                    MeshMaterial2d(rock_material.clone()),
-                    Transform::default()
-                        .with_scale(Vec3::splat(TILE_SIZE))
-                        .with_translation(Vec3::new(
-                            x as f32 * TILE_SIZE,
-                            y as f32 * TILE_SIZE,
-                            1.0,
-                        )),
-                    Pickable::default(),
-                ))
-                .observe(recolor_on::<Pointer<Over>>(hover_matl.clone()))
-                .observe(recolor_on::<Pointer<Out>>(rock_material.clone()));
-                
-                // Override with unpassable in tile map
-                tile_map.insert((x, y), TileType::Unpassable);
-            }
+            .observe(recolor_on::<Pointer<Out>>(material.clone()))
+            .observe(set_destination_on_click((x, y)));
 
+            tile_map.insert((x, y), ttype);
+            biome_map.0.insert((x, y), biome);
+        }
+    }
 
-            let noise_val = perlin.get([x as f64 / NOISE_SCALE, y as f64 / NOISE_SCALE]);
+    // Spawn a handful of hostile enemies on random passable tiles.
+    let enemy_matl = materials.add(Color::from(RED));
+    let flash_matl = materials.add(Color::from(YELLOW));
 
-            if noise_val > 0.8 {
-                let rock_material = rock_matl.clone();
-                commands.spawn((
-                    Tile { pos: (x, y), ttype: TileType::Unpassable },
-                    Mesh2d(meshes.add(Rectangle::default())),
-                    MeshMaterial2d(water_matl.clone()),
-                    Transform::default()
-                        .with_scale(Vec3::splat(TILE_SIZE))
-                        .with_translation(Vec3::new(
-                            x as f32 * TILE_SIZE,
-                            y as f32 * TILE_SIZE,
-                            1.0,
-                        )),
-                    Pickable::default(),
-                ))
-                .observe(recolor_on::<Pointer<Over>>(hover_matl.clone()))
-                .observe(recolor_on::<Pointer<Out>>(water_matl.clone()));
-                
-                // Override with unpassable in tile map
-                tile_map.insert((x, y), TileType::Unpassable);
+    for _ in 0..ENEMY_COUNT {
+        let pos = loop {
+            let candidate = (
+                rng.gen_range(0..GRID_SIZE as i32),
+                rng.gen_range(0..GRID_SIZE as i32),
+            );
+            if tile_map.is_passable(candidate) {
+                break candidate;
             }
-        }
+        };
+
+        let enemy_material = enemy_matl.clone();
+        commands
+            .spawn((
+                Enemy { pos },
+                Faction { hostile_to_player: true },
+                Mesh2d(meshes.add(Rectangle::default())),
+                MeshMaterial2d(enemy_material.clone()),
+                Transform::default()
+                    .with_scale(Vec3::splat(TILE_SIZE))
+                    .with_translation(Vec3::new(
+                        pos.0 as f32 * TILE_SIZE,
+                        pos.1 as f32 * TILE_SIZE,
+                        5.0,
+                    )),
+            ))
+            .observe(recolor_on::<Attacked>(flash_matl.clone()))
+            .observe(start_flash_timer)
+            .observe(recolor_on::<AttackEnded>(enemy_material.clone()));
     }
 }
 